@@ -0,0 +1,96 @@
+//! A minimal complex number type, used throughout the crate's linear algebra.
+
+use std::ops::{Add, Mul, Neg, Sub};
+
+/// A complex number `re + im*i`.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Complex {
+    pub re: f64,
+    pub im: f64,
+}
+
+impl Complex {
+    /// Build a complex number from its real and imaginary parts.
+    pub fn new(re: f64, im: f64) -> Complex {
+        Complex { re: re, im: im }
+    }
+
+    /// The additive identity, `0`.
+    pub fn zero() -> Complex {
+        Complex::new(0f64, 0f64)
+    }
+
+    /// The multiplicative identity, `1`.
+    pub fn one() -> Complex {
+        Complex::new(1f64, 0f64)
+    }
+
+    /// The imaginary unit, `i`.
+    pub fn i() -> Complex {
+        Complex::new(0f64, 1f64)
+    }
+
+    /// A complex number in polar form, `r * e^{i*theta}`.
+    pub fn new_euler(r: f64, theta: f64) -> Complex {
+        Complex::new(r * theta.cos(), r * theta.sin())
+    }
+
+    /// The modulus (absolute value) `|z|`.
+    pub fn modulus(&self) -> f64 {
+        (self.re * self.re + self.im * self.im).sqrt()
+    }
+
+    /// The argument (phase angle) `arg(z)`, in `(-pi, pi]`.
+    ///
+    /// `arg(0)` is conventionally `0`.
+    pub fn arg(&self) -> f64 {
+        self.im.atan2(self.re)
+    }
+
+    /// The complex conjugate, `re - im*i`.
+    pub fn conj(&self) -> Complex {
+        Complex::new(self.re, -self.im)
+    }
+}
+
+impl Add for Complex {
+    type Output = Complex;
+
+    fn add(self, rhs: Complex) -> Complex {
+        Complex::new(self.re + rhs.re, self.im + rhs.im)
+    }
+}
+
+impl Sub for Complex {
+    type Output = Complex;
+
+    fn sub(self, rhs: Complex) -> Complex {
+        Complex::new(self.re - rhs.re, self.im - rhs.im)
+    }
+}
+
+impl Mul for Complex {
+    type Output = Complex;
+
+    fn mul(self, rhs: Complex) -> Complex {
+        Complex::new(self.re * rhs.re - self.im * rhs.im,
+                     self.re * rhs.im + self.im * rhs.re)
+    }
+}
+
+impl Neg for Complex {
+    type Output = Complex;
+
+    fn neg(self) -> Complex {
+        Complex::new(-self.re, -self.im)
+    }
+}
+
+/// Convenience macro for building a [`Complex`](struct.Complex.html) from a
+/// real and imaginary part.
+#[macro_export]
+macro_rules! c {
+    ($re:expr, $im:expr) => {
+        Complex::new($re as f64, $im as f64)
+    };
+}
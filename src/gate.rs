@@ -0,0 +1,125 @@
+//! The [`Gate`](struct.Gate.html) type: an operator that can be applied to a
+//! [`Ket`](../ket/struct.Ket.html).
+
+use complex::Complex;
+use matrix::Matrix;
+
+/// A quantum gate acting on a `width`-qubit register.
+///
+/// Most gates carry their dense `2^width * 2^width` matrix directly, built by
+/// [`new`](Gate::new). The [`single_on`](Gate::single_on) and
+/// [`controlled_on`](Gate::controlled_on) constructors instead keep only the
+/// `2x2` operator together with the qubit(s) it acts on, letting
+/// [`Ket::apply`](../ket/struct.Ket.html#method.apply) skip the dense
+/// expansion and its `O(4^width)` product entirely.
+#[derive(Clone, Debug, PartialEq)]
+pub enum Gate {
+    /// A gate given by its full `2^width * 2^width` matrix.
+    Dense { width: usize, matrix: Matrix },
+    /// A single-qubit gate acting on `target`, applied matrix-free.
+    SingleOn { width: usize, target: usize, matrix: Matrix },
+    /// A single-qubit gate acting on `target`, conditioned on every bit of
+    /// `controls` being set, applied matrix-free.
+    ControlledOn { width: usize, controls: Vec<usize>, target: usize, matrix: Matrix },
+}
+
+impl Gate {
+    /// Build a gate from its full `width`-qubit matrix.
+    #[allow(unused)]
+    pub fn new(width: usize, matrix: Matrix) -> Gate {
+        Gate::Dense { width: width, matrix: matrix }
+    }
+
+    /// The number of qubits this gate acts on.
+    #[allow(unused)]
+    pub fn width(&self) -> usize {
+        match *self {
+            Gate::Dense { width, .. } => width,
+            Gate::SingleOn { width, .. } => width,
+            Gate::ControlledOn { width, .. } => width,
+        }
+    }
+
+    /// The gate's matrix.
+    ///
+    /// For [`single_on`](Gate::single_on)/[`controlled_on`](Gate::controlled_on)
+    /// gates this is the `2x2` operator they were built from, not the dense
+    /// `2^width * 2^width` expansion.
+    #[allow(unused)]
+    pub fn matrix(&self) -> &Matrix {
+        match *self {
+            Gate::Dense { ref matrix, .. } => matrix,
+            Gate::SingleOn { ref matrix, .. } => matrix,
+            Gate::ControlledOn { ref matrix, .. } => matrix,
+        }
+    }
+
+    /// A single-qubit gate acting on the `target` bit of a `width`-qubit
+    /// register, applied matrix-free.
+    ///
+    /// Rather than expanding `u` into a dense `2^width` matrix, the returned
+    /// gate is applied by [`Ket::apply`](../ket/struct.Ket.html#method.apply)
+    /// directly over the amplitude pairs `(i, i | 1 << (width - 1 - target))`
+    /// (qubit `0` is the most significant bit of the index), turning an
+    /// `O(4^width)` product into an `O(2^width)` sweep.
+    ///
+    /// # Panics
+    ///
+    /// We panic if the supplied matrix isn't of size 2x2.
+    #[allow(unused)]
+    pub fn single_on(width: usize, target: usize, u: &Matrix) -> Gate {
+        assert_eq!(2, u.size());
+
+        Gate::SingleOn { width: width, target: target, matrix: u.clone() }
+    }
+
+    /// A controlled single-qubit gate acting on `target`, conditioned on
+    /// every bit in `controls` being set, applied matrix-free.
+    ///
+    /// This shares the fast path of [`single_on`](Gate::single_on), skipping
+    /// any amplitude pair whose control bits are not all set.
+    ///
+    /// # Panics
+    ///
+    /// We panic if the supplied matrix isn't of size 2x2.
+    #[allow(unused)]
+    pub fn controlled_on(width: usize, controls: &[usize], target: usize, u: &Matrix) -> Gate {
+        assert_eq!(2, u.size());
+
+        Gate::ControlledOn {
+            width: width,
+            controls: controls.to_vec(),
+            target: target,
+            matrix: u.clone(),
+        }
+    }
+
+    /// Multiply this gate's matrix by `e^{i*phase}`.
+    ///
+    /// This is how a tracked global phase (e.g.
+    /// [`QasmGate::global_phase`](../openqasm/enum.QasmGate.html#method.global_phase))
+    /// gets folded back into the concrete matrix a gate is built into, so
+    /// that the result matches a reference matrix exactly rather than only
+    /// up to a phase.
+    #[allow(unused)]
+    pub fn with_global_phase(self, phase: f64) -> Gate {
+        let factor = Complex::new_euler(1f64, phase);
+
+        match self {
+            Gate::Dense { width, matrix } => {
+                Gate::Dense { width: width, matrix: matrix.scale(factor) }
+            }
+            Gate::SingleOn { width, target, matrix } => {
+                Gate::SingleOn { width: width, target: target, matrix: matrix.scale(factor) }
+            }
+            Gate::ControlledOn { width, controls, target, matrix } => {
+                Gate::ControlledOn {
+                    width: width,
+                    controls: controls,
+                    target: target,
+                    matrix: matrix.scale(factor),
+                }
+            }
+        }
+    }
+}
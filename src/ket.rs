@@ -0,0 +1,136 @@
+//! The [`Ket`](struct.Ket.html) type: a quantum state vector.
+
+#[cfg(feature = "parallel")]
+use rayon::prelude::*;
+
+use complex::Complex;
+use gate::Gate;
+#[cfg(feature = "parallel")]
+use gates::PARALLEL_THRESHOLD;
+use matrix::Matrix;
+
+/// A state vector over `2^width` amplitudes, for a `width`-qubit register.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Ket {
+    pub elements: Vec<Complex>,
+}
+
+impl Ket {
+    /// A new, all-zero state vector of `size` amplitudes.
+    #[allow(unused)]
+    pub fn new(size: usize) -> Ket {
+        Ket { elements: vec![Complex::zero(); size] }
+    }
+
+    /// The number of amplitudes in a `width`-qubit register, `2^width`.
+    #[allow(unused)]
+    pub fn size(width: usize) -> usize {
+        1 << width
+    }
+
+    /// Apply `gate` to this state vector in place.
+    #[allow(unused)]
+    pub fn apply(&mut self, gate: Gate) {
+        match gate {
+            Gate::Dense { matrix, .. } => self.apply_dense(&matrix),
+            Gate::SingleOn { target, matrix, .. } => {
+                self.apply_matrix_free(target, &[], &matrix)
+            }
+            Gate::ControlledOn { controls, target, matrix, .. } => {
+                self.apply_matrix_free(target, &controls, &matrix)
+            }
+        }
+    }
+
+    /// The dense `O(4^width)` application path: a full `Matrix` x `Ket`
+    /// product.
+    ///
+    /// At or above [`PARALLEL_THRESHOLD`](../gates/constant.PARALLEL_THRESHOLD.html)
+    /// qubits, the `parallel` feature fans the per-row dot products out
+    /// across rayon's thread pool; each row only reads `self.elements` and
+    /// writes its own slot in `next`, so there's no data race to guard
+    /// against.
+    #[allow(unused_variables)]
+    fn apply_dense(&mut self, matrix: &Matrix) {
+        let size = self.elements.len();
+        let width = size.trailing_zeros() as usize;
+        let mut next = vec![Complex::zero(); size];
+
+        let row = |row: usize| {
+            (0..size).fold(Complex::zero(), |sum, col| sum + matrix.get(row, col) * self.elements[col])
+        };
+
+        #[cfg(feature = "parallel")]
+        {
+            if width >= PARALLEL_THRESHOLD {
+                next.par_iter_mut().enumerate().for_each(|(r, out)| *out = row(r));
+                self.elements = next;
+                return;
+            }
+        }
+
+        for r in 0..size {
+            next[r] = row(r);
+        }
+
+        self.elements = next;
+    }
+
+    /// The matrix-free `O(2^width)` application path shared by
+    /// [`Gate::single_on`](../gate/enum.Gate.html#method.single_on) and
+    /// [`Gate::controlled_on`](../gate/enum.Gate.html#method.controlled_on):
+    /// sweep the amplitude pairs `(i, i | 1 << (width - 1 - target))` whose
+    /// control bits are all set and mix each pair through `u` in place.
+    /// Qubit `0` is the most significant bit of the amplitude index.
+    ///
+    /// At or above [`PARALLEL_THRESHOLD`](../gates/constant.PARALLEL_THRESHOLD.html)
+    /// qubits, the `parallel` feature computes the mixed pairs across
+    /// rayon's thread pool before scattering them back in; each pair only
+    /// touches its own two amplitudes, so the pairs are independent.
+    #[allow(unused_variables)]
+    fn apply_matrix_free(&mut self, target: usize, controls: &[usize], u: &Matrix) {
+        let size = self.elements.len();
+        let width = size.trailing_zeros() as usize;
+        let target_bit = 1 << (width - 1 - target);
+        let control_mask = controls.iter().fold(0, |mask, &c| mask | (1 << (width - 1 - c)));
+
+        let u00 = u.get(0, 0);
+        let u01 = u.get(0, 1);
+        let u10 = u.get(1, 0);
+        let u11 = u.get(1, 1);
+
+        let pairs: Vec<usize> =
+            (0..size).filter(|i| i & target_bit == 0 && i & control_mask == control_mask).collect();
+
+        #[cfg(feature = "parallel")]
+        {
+            if width >= PARALLEL_THRESHOLD {
+                let updates: Vec<(Complex, Complex)> = pairs.par_iter()
+                    .map(|&i| {
+                        let j = i | target_bit;
+                        let a = self.elements[i];
+                        let b = self.elements[j];
+
+                        (u00 * a + u01 * b, u10 * a + u11 * b)
+                    })
+                    .collect();
+
+                for (&i, (a, b)) in pairs.iter().zip(updates) {
+                    self.elements[i] = a;
+                    self.elements[i | target_bit] = b;
+                }
+
+                return;
+            }
+        }
+
+        for i in pairs {
+            let j = i | target_bit;
+            let a = self.elements[i];
+            let b = self.elements[j];
+
+            self.elements[i] = u00 * a + u01 * b;
+            self.elements[j] = u10 * a + u11 * b;
+        }
+    }
+}
@@ -0,0 +1,107 @@
+//! A dense, square, complex-valued matrix, used to represent gate operators.
+
+use complex::Complex;
+
+/// A square matrix of complex numbers, stored row-major.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Matrix {
+    size: usize,
+    elements: Vec<Complex>,
+}
+
+impl Matrix {
+    /// Build a matrix from a flat, row-major buffer of `size * size` entries.
+    ///
+    /// # Panics
+    ///
+    /// We panic if `elements.len() != size * size`.
+    pub fn new(size: usize, elements: Vec<Complex>) -> Matrix {
+        assert_eq!(size * size, elements.len());
+
+        Matrix { size: size, elements: elements }
+    }
+
+    /// The `size * size` identity matrix.
+    pub fn identity(size: usize) -> Matrix {
+        let mut elements = vec![Complex::zero(); size * size];
+
+        for i in 0..size {
+            elements[i * size + i] = Complex::one();
+        }
+
+        Matrix::new(size, elements)
+    }
+
+    /// This matrix's dimension `n`, for an `n * n` matrix.
+    pub fn size(&self) -> usize {
+        self.size
+    }
+
+    /// The entry at `(row, col)`.
+    pub fn get(&self, row: usize, col: usize) -> Complex {
+        self.elements[row * self.size + col]
+    }
+
+    /// Overwrite the entry at `(row, col)`.
+    pub fn set(&mut self, row: usize, col: usize, value: Complex) {
+        self.elements[row * self.size + col] = value;
+    }
+
+    /// Overwrite the `other.size() x other.size()` block of `self` starting
+    /// at `(row, col)` with the entries of `other`.
+    pub fn embed(&mut self, other: &Matrix, row: usize, col: usize) {
+        for r in 0..other.size {
+            for c in 0..other.size {
+                let value = other.get(r, c);
+                self.set(row + r, col + c, value);
+            }
+        }
+    }
+
+    /// Multiply every entry by `factor`.
+    #[allow(unused)]
+    pub fn scale(&self, factor: Complex) -> Matrix {
+        let elements = self.elements.iter().map(|&e| factor * e).collect();
+
+        Matrix::new(self.size, elements)
+    }
+}
+
+/// Convenience macro for building a [`Matrix`](struct.Matrix.html) out of
+/// [`Complex`](../complex/struct.Complex.html) expressions, rows separated
+/// by `;`.
+#[macro_export]
+macro_rules! m {
+    ( $( $($val:expr),+ );+ $(;)* ) => {{
+        let rows: Vec<Vec<Complex>> = vec![ $( vec![ $($val),+ ] ),+ ];
+        let size = rows.len();
+        let mut elements = Vec::with_capacity(size * size);
+
+        for row in rows {
+            for value in row {
+                elements.push(value);
+            }
+        }
+
+        Matrix::new(size, elements)
+    }};
+}
+
+/// Like [`m!`](macro.m.html), but for real-valued entries given as bare
+/// numeric literals or expressions.
+#[macro_export]
+macro_rules! m_real {
+    ( $( $($val:expr),+ );+ $(;)* ) => {{
+        let rows: Vec<Vec<Complex>> = vec![ $( vec![ $(Complex::new($val as f64, 0f64)),+ ] ),+ ];
+        let size = rows.len();
+        let mut elements = Vec::with_capacity(size * size);
+
+        for row in rows {
+            for value in row {
+                elements.push(value);
+            }
+        }
+
+        Matrix::new(size, elements)
+    }};
+}
@@ -0,0 +1,291 @@
+//! Import and export of this crate's gate set as OpenQASM 3 standard-library
+//! gate declarations.
+//!
+//! Every variant of [`QasmGate`] corresponds to a gate from the OpenQASM
+//! standard library. It can be [emitted](QasmGate::to_qasm) as a declaration,
+//! [parsed](QasmGate::from_qasm) back from one, and [built](QasmGate::build)
+//! into a concrete [`Gate`]. The accumulated [global phase](QasmGate::global_phase)
+//! is tracked so that a round-trip preserves the exact matrix and not merely
+//! its projective equivalence class. Going the other way, an already-built
+//! [`Gate`] from the fixed (unparametrized) subset of the standard library
+//! can be mapped back to its declaration with [`QasmGate::from_gate`].
+
+use gate::Gate;
+use gates::{controlled_not, controlled_z, fredkin, hadamard, pauli_x, pauli_y, pauli_z, rx, ry,
+            rz, s, swap, t, toffoli, u};
+use matrix::Matrix;
+
+/// A gate from the OpenQASM 3 standard library.
+#[derive(Clone, Copy, Debug, PartialEq)]
+#[allow(unused)]
+pub enum QasmGate {
+    /// `x` — Pauli-X.
+    X,
+    /// `y` — Pauli-Y.
+    Y,
+    /// `z` — Pauli-Z.
+    Z,
+    /// `h` — Hadamard.
+    H,
+    /// `s` — phase gate.
+    S,
+    /// `t` — T gate.
+    T,
+    /// `rx(θ)` — rotation about the x-axis.
+    Rx(f64),
+    /// `ry(θ)` — rotation about the y-axis.
+    Ry(f64),
+    /// `rz(λ)` — rotation about the z-axis.
+    Rz(f64),
+    /// `U(θ, φ, λ)` — the universal single-qubit gate.
+    U(f64, f64, f64),
+    /// `swap` — the two qubit swap.
+    Swap,
+    /// `cx` — controlled-X.
+    Cx,
+    /// `cz` — controlled-Z.
+    Cz,
+    /// `ccx` — the Toffoli gate.
+    Ccx,
+    /// `cswap` — the Fredkin gate.
+    Cswap,
+}
+
+#[allow(unused)]
+impl QasmGate {
+    /// The global phase this crate's matrix for the gate carries relative to
+    /// the OpenQASM standard-library definition.
+    ///
+    /// Per `stdgates.inc`, only `rz` is defined with an explicit `gphase`
+    /// term (`gate rz(λ) a { gphase(-λ/2); U(0,0,λ) a; }`); `h`, `rx`, and
+    /// `ry` carry none, and indeed this crate's [`hadamard`](../gates/fn.hadamard.html)
+    /// already matches `u(π/2, 0, π)` up to floating-point rounding. Emitting the matching
+    /// `gphase` term for `rz` keeps the round-trip exact.
+    pub fn global_phase(&self) -> f64 {
+        match *self {
+            QasmGate::Rz(lambda) => -lambda / 2.0,
+            _ => 0.0,
+        }
+    }
+
+    /// Emit the OpenQASM declaration for this gate, appending the tracked
+    /// `gphase` term when the gate carries a non-zero global phase.
+    pub fn to_qasm(&self) -> String {
+        let decl = match *self {
+            QasmGate::X => "x".to_string(),
+            QasmGate::Y => "y".to_string(),
+            QasmGate::Z => "z".to_string(),
+            QasmGate::H => "h".to_string(),
+            QasmGate::S => "s".to_string(),
+            QasmGate::T => "t".to_string(),
+            QasmGate::Rx(theta) => format!("rx({})", theta),
+            QasmGate::Ry(theta) => format!("ry({})", theta),
+            QasmGate::Rz(lambda) => format!("rz({})", lambda),
+            QasmGate::U(theta, phi, lambda) => format!("U({}, {}, {})", theta, phi, lambda),
+            QasmGate::Swap => "swap".to_string(),
+            QasmGate::Cx => "cx".to_string(),
+            QasmGate::Cz => "cz".to_string(),
+            QasmGate::Ccx => "ccx".to_string(),
+            QasmGate::Cswap => "cswap".to_string(),
+        };
+
+        let phase = self.global_phase();
+
+        if phase != 0.0 {
+            format!("{}; gphase({})", decl, phase)
+        } else {
+            decl
+        }
+    }
+
+    /// Parse a single OpenQASM gate declaration.
+    ///
+    /// Any trailing `gphase` term is discarded, since the phase is recovered
+    /// deterministically from the gate itself via [`global_phase`](QasmGate::global_phase).
+    /// Returns `None` for a declaration outside the supported standard-library
+    /// subset.
+    pub fn from_qasm(decl: &str) -> Option<QasmGate> {
+        // Drop a trailing `gphase` term and the statement terminator.
+        let decl = decl.split(';').next().unwrap_or("").trim();
+
+        let (name, params) = match decl.find('(') {
+            Some(open) => {
+                let name = decl[..open].trim();
+                let inner = decl[open + 1..].trim_end_matches(')');
+                let params: Vec<f64> =
+                    inner.split(',').filter_map(|p| p.trim().parse().ok()).collect();
+
+                (name, params)
+            }
+            None => (decl, Vec::new()),
+        };
+
+        match name {
+            "x" => Some(QasmGate::X),
+            "y" => Some(QasmGate::Y),
+            "z" => Some(QasmGate::Z),
+            "h" => Some(QasmGate::H),
+            "s" => Some(QasmGate::S),
+            "t" => Some(QasmGate::T),
+            "rx" if params.len() == 1 => Some(QasmGate::Rx(params[0])),
+            "ry" if params.len() == 1 => Some(QasmGate::Ry(params[0])),
+            "rz" if params.len() == 1 => Some(QasmGate::Rz(params[0])),
+            "U" | "u" if params.len() == 3 => Some(QasmGate::U(params[0], params[1], params[2])),
+            "swap" => Some(QasmGate::Swap),
+            "cx" => Some(QasmGate::Cx),
+            "cz" => Some(QasmGate::Cz),
+            "ccx" => Some(QasmGate::Ccx),
+            "cswap" => Some(QasmGate::Cswap),
+            _ => None,
+        }
+    }
+
+    /// Build the concrete [`Gate`] for this declaration via the
+    /// [`gates`](../gates/index.html) builders, with the tracked
+    /// [`global_phase`](QasmGate::global_phase) folded into the matrix so the
+    /// result matches the OpenQASM definition exactly, not just up to phase.
+    pub fn build(&self) -> Gate {
+        let gate = match *self {
+            QasmGate::X => pauli_x(),
+            QasmGate::Y => pauli_y(),
+            QasmGate::Z => pauli_z(),
+            QasmGate::H => hadamard(),
+            QasmGate::S => s(),
+            QasmGate::T => t(),
+            QasmGate::Rx(theta) => rx(theta),
+            QasmGate::Ry(theta) => ry(theta),
+            QasmGate::Rz(lambda) => rz(lambda),
+            QasmGate::U(theta, phi, lambda) => u(theta, phi, lambda),
+            QasmGate::Swap => swap(),
+            QasmGate::Cx => controlled_not(),
+            QasmGate::Cz => controlled_z(),
+            QasmGate::Ccx => toffoli(),
+            QasmGate::Cswap => fredkin(),
+        };
+
+        let phase = self.global_phase();
+
+        if phase != 0.0 { gate.with_global_phase(phase) } else { gate }
+    }
+
+    /// Recover the `QasmGate` whose [`build`](QasmGate::build) matches
+    /// `gate`'s matrix, for the fixed (unparametrized) subset of the
+    /// standard library.
+    ///
+    /// This is the inverse of `build` for a `Gate` a caller already has in
+    /// hand -- e.g. one they got back from [`hadamard`](../gates/fn.hadamard.html)
+    /// or [`toffoli`](../gates/fn.toffoli.html) -- rather than a `QasmGate`
+    /// they picked themselves.
+    ///
+    /// Scope cut: parametrized gates (`Rx`/`Ry`/`Rz`/`U`) aren't recovered
+    /// this way. A matrix alone doesn't carry its own angles back out
+    /// without redoing a decomposition (see
+    /// [`zyz_decompose`](../gates/fn.zyz_decompose.html)), so exporting
+    /// those still requires knowing the parameters up front and building a
+    /// `QasmGate` directly.
+    pub fn from_gate(gate: &Gate) -> Option<QasmGate> {
+        const FIXED: &[QasmGate] = &[QasmGate::X,
+                                      QasmGate::Y,
+                                      QasmGate::Z,
+                                      QasmGate::H,
+                                      QasmGate::S,
+                                      QasmGate::T,
+                                      QasmGate::Swap,
+                                      QasmGate::Cx,
+                                      QasmGate::Cz,
+                                      QasmGate::Ccx,
+                                      QasmGate::Cswap];
+
+        FIXED.iter().cloned().find(|candidate| {
+            let built = candidate.build();
+
+            built.width() == gate.width() && matrices_close(built.matrix(), gate.matrix())
+        })
+    }
+}
+
+/// Whether `a` and `b` agree entrywise within floating-point tolerance.
+fn matrices_close(a: &Matrix, b: &Matrix) -> bool {
+    if a.size() != b.size() {
+        return false;
+    }
+
+    (0..a.size())
+        .all(|r| (0..a.size()).all(|c| (a.get(r, c) - b.get(r, c)).modulus() < 1e-9))
+}
+
+#[test]
+fn round_trip_test() {
+    let gates = [QasmGate::X,
+                 QasmGate::Y,
+                 QasmGate::Z,
+                 QasmGate::H,
+                 QasmGate::S,
+                 QasmGate::T,
+                 QasmGate::Rx(0.3),
+                 QasmGate::Ry(-1.2),
+                 QasmGate::Rz(0.7),
+                 QasmGate::U(0.3, 0.1, -0.4),
+                 QasmGate::Swap,
+                 QasmGate::Cx,
+                 QasmGate::Cz,
+                 QasmGate::Ccx,
+                 QasmGate::Cswap];
+
+    for g in gates.iter() {
+        assert_eq!(Some(*g), QasmGate::from_qasm(&g.to_qasm()));
+    }
+}
+
+#[test]
+fn global_phase_test() {
+    // Only `rz` is defined via an explicit `gphase` in `stdgates.inc`; `h`
+    // and the other rotations carry none.
+    assert_eq!(-0.35, QasmGate::Rz(0.7).global_phase());
+    assert_eq!(0.0, QasmGate::H.global_phase());
+    assert_eq!(0.0, QasmGate::Rx(0.3).global_phase());
+    assert_eq!(0.0, QasmGate::X.global_phase());
+
+    // The emitted declaration carries the tracked term.
+    assert_eq!("rz(0.7); gphase(-0.35)", QasmGate::Rz(0.7).to_qasm());
+    assert_eq!("h", QasmGate::H.to_qasm());
+    assert_eq!("x", QasmGate::X.to_qasm());
+}
+
+#[test]
+fn build_applies_global_phase_test() {
+    use complex::Complex;
+
+    // `build()` must fold `global_phase()` into the returned matrix, not
+    // just track it for display: our `rz(lambda)` is `diag(1, e^{i*lambda})`,
+    // so applying its `-lambda/2` phase should yield the symmetric
+    // `diag(e^{-i*lambda/2}, e^{i*lambda/2})` that OpenQASM's `rz` defines.
+    let lambda = 0.7f64;
+    let m = QasmGate::Rz(lambda).build().matrix().clone();
+
+    let expected00 = Complex::new_euler(1f64, -lambda / 2.0);
+    let expected11 = Complex::new_euler(1f64, lambda / 2.0);
+
+    assert!((m.get(0, 0) - expected00).modulus() < 1e-9);
+    assert!((m.get(0, 1)).modulus() < 1e-9);
+    assert!((m.get(1, 0)).modulus() < 1e-9);
+    assert!((m.get(1, 1) - expected11).modulus() < 1e-9);
+}
+
+#[test]
+fn from_gate_test() {
+    // A constructed Gate maps back to its declaration...
+    assert_eq!(Some(QasmGate::H), QasmGate::from_gate(&hadamard()));
+    assert_eq!(Some(QasmGate::Ccx), QasmGate::from_gate(&toffoli()));
+    assert_eq!(Some(QasmGate::Swap), QasmGate::from_gate(&swap()));
+
+    // ...but a parametrized gate's matrix alone doesn't carry its angles
+    // back out, so it isn't recovered.
+    assert_eq!(None, QasmGate::from_gate(&rx(0.3)));
+}
+
+#[test]
+fn unknown_declaration_test() {
+    assert_eq!(None, QasmGate::from_qasm("measure"));
+    assert_eq!(None, QasmGate::from_qasm("rx"));
+}